@@ -0,0 +1,189 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{
+        actor::{ActorHandle, MessageStop},
+        index::Index,
+        snapshot::{self, Watermark},
+        ColumnName, Connectivity, Dimensions, ExpansionAdd, ExpansionSearch, IndexId,
+    },
+    std::path::{Path, PathBuf},
+    tokio::sync::{mpsc, oneshot},
+    tracing::warn,
+};
+
+const MANIFEST_FILE: &str = "manifest";
+const MANIFEST_VERSION: u32 = 1;
+
+/// One index definition plus a live handle to export its graph, as seen by `DumpCreate`.
+pub(crate) struct ExportEntry {
+    pub(crate) id: IndexId,
+    pub(crate) col_id: ColumnName,
+    pub(crate) col_emb: ColumnName,
+    pub(crate) dimensions: Dimensions,
+    pub(crate) connectivity: Connectivity,
+    pub(crate) expansion_add: ExpansionAdd,
+    pub(crate) expansion_search: ExpansionSearch,
+    pub(crate) watermark: Watermark,
+    pub(crate) actor: mpsc::Sender<Index>,
+}
+
+/// One index definition recovered from a dump's manifest, ready to be re-issued as
+/// `AddIndex` plus a graph-import step.
+pub(crate) struct ImportEntry {
+    pub(crate) id: IndexId,
+    pub(crate) col_id: ColumnName,
+    pub(crate) col_emb: ColumnName,
+    pub(crate) dimensions: Dimensions,
+    pub(crate) connectivity: Connectivity,
+    pub(crate) expansion_add: ExpansionAdd,
+    pub(crate) expansion_search: ExpansionSearch,
+    pub(crate) watermark: Watermark,
+}
+
+fn manifest_line(entry: &ExportEntry) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        entry.id,
+        entry.col_id,
+        entry.col_emb,
+        entry.dimensions,
+        entry.connectivity,
+        entry.expansion_add,
+        entry.expansion_search,
+        entry.watermark.0,
+    )
+}
+
+fn decode_manifest_line(line: &str) -> Option<ImportEntry> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [id, col_id, col_emb, dimensions, connectivity, expansion_add, expansion_search, watermark] =
+        fields[..]
+    else {
+        return None;
+    };
+    Some(ImportEntry {
+        id: IndexId::from(id.to_string()),
+        col_id: ColumnName::from(col_id.to_string()),
+        col_emb: ColumnName::from(col_emb.to_string()),
+        dimensions: dimensions.parse::<u32>().ok()?.into(),
+        connectivity: connectivity.parse::<u32>().ok()?.into(),
+        expansion_add: expansion_add.parse::<u32>().ok()?.into(),
+        expansion_search: expansion_search.parse::<u32>().ok()?.into(),
+        watermark: Watermark(watermark.parse().ok()?),
+    })
+}
+
+/// Serializes the whole engine state -- every index definition plus its usearch graph --
+/// into a single versioned, self-describing archive directory, following the same
+/// `save`/`load` primitives as [`snapshot`] so a re-provisioned node resumes ingestion from
+/// the same watermark the dump was taken at instead of rescanning ScyllaDB from scratch.
+pub(crate) async fn create(dir: &Path, entries: Vec<ExportEntry>) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    let mut manifest = format!("DUMP_VERSION\t{MANIFEST_VERSION}\n");
+    for entry in &entries {
+        snapshot::save(dir, &entry.id, &entry.actor, entry.watermark).await?;
+        manifest.push_str(&manifest_line(entry));
+    }
+    tokio::fs::write(dir.join(MANIFEST_FILE), manifest).await?;
+    Ok(())
+}
+
+/// Reads a dump's manifest, returning the index definitions it describes (including the
+/// watermark ingestion had reached when the dump was taken) plus the path to each graph
+/// file, so the caller can re-provision a node without re-scanning ScyllaDB.
+pub(crate) async fn load(dir: &Path) -> anyhow::Result<Vec<(ImportEntry, PathBuf)>> {
+    let manifest = tokio::fs::read_to_string(dir.join(MANIFEST_FILE)).await?;
+    let mut lines = manifest.lines();
+    let version_line = lines
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("dump at {dir:?} has an empty manifest"))?;
+    if version_line != format!("DUMP_VERSION\t{MANIFEST_VERSION}") {
+        anyhow::bail!("dump at {dir:?} has an unsupported manifest version: {version_line}");
+    }
+    let mut entries = Vec::new();
+    for line in lines {
+        let Some(entry) = decode_manifest_line(line) else {
+            warn!("dump::load: skipping malformed manifest line in {dir:?}: {line}");
+            continue;
+        };
+        let graph_path = snapshot::index_path(dir, &entry.id);
+        entries.push((entry, graph_path));
+    }
+    Ok(entries)
+}
+
+pub(crate) enum DumpTask {
+    Stop,
+}
+
+impl MessageStop for DumpTask {
+    fn message_stop() -> Self {
+        DumpTask::Stop
+    }
+}
+
+/// Runs `create` as a one-shot background task registered with the `Supervisor`, so a slow
+/// dump of many large graphs doesn't block the `Engine`'s own message loop.
+pub(crate) async fn spawn(
+    dir: PathBuf,
+    entries: Vec<ExportEntry>,
+    result_tx: oneshot::Sender<anyhow::Result<()>>,
+) -> (mpsc::Sender<DumpTask>, ActorHandle) {
+    let (tx, mut rx) = mpsc::channel(1);
+    let task = tokio::spawn(async move {
+        let result = create(&dir, entries).await;
+        result_tx
+            .send(result)
+            .unwrap_or_else(|_| warn!("dump::spawn: unable to send response"));
+        while let Some(msg) = rx.recv().await {
+            match msg {
+                DumpTask::Stop => rx.close(),
+            }
+        }
+    });
+    (tx, task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export_entry(watermark: i64) -> ExportEntry {
+        let (actor, _rx) = mpsc::channel(1);
+        ExportEntry {
+            id: IndexId::from("my_index".to_string()),
+            col_id: ColumnName::from("id".to_string()),
+            col_emb: ColumnName::from("embedding".to_string()),
+            dimensions: 768.into(),
+            connectivity: 16.into(),
+            expansion_add: 128.into(),
+            expansion_search: 64.into(),
+            watermark: Watermark(watermark),
+            actor,
+        }
+    }
+
+    #[test]
+    fn manifest_line_round_trips_through_decode() {
+        let entry = export_entry(42);
+        let line = manifest_line(&entry);
+        let decoded = decode_manifest_line(line.trim_end()).expect("manifest line should decode");
+        assert_eq!(decoded.id.to_string(), entry.id.to_string());
+        assert_eq!(decoded.col_id.to_string(), entry.col_id.to_string());
+        assert_eq!(decoded.col_emb.to_string(), entry.col_emb.to_string());
+        assert_eq!(decoded.dimensions.0, entry.dimensions.0);
+        assert_eq!(decoded.connectivity.0, entry.connectivity.0);
+        assert_eq!(decoded.expansion_add.0, entry.expansion_add.0);
+        assert_eq!(decoded.expansion_search.0, entry.expansion_search.0);
+        assert_eq!(decoded.watermark.0, entry.watermark.0);
+    }
+
+    #[test]
+    fn decode_manifest_line_rejects_malformed_line() {
+        assert!(decode_manifest_line("too\tfew\tfields").is_none());
+    }
+}
@@ -6,17 +6,53 @@
 use {
     crate::{
         actor::{ActorHandle, ActorStop, MessageStop},
-        index::{self, Index},
+        dump,
+        index::{self, Index, IndexExt},
         modify_indexes::{self, ModifyIndexesExt},
         monitor_indexes, monitor_items, monitor_queries,
+        op_queue::{Op, OpQueue},
+        snapshot::{self, SnapshotConfig, Watermark},
         supervisor::{Supervisor, SupervisorExt},
+        task_store::{self, Task, TaskFilter, TaskId, TaskKind, TaskStoreExt},
         ColumnName, Connectivity, Dimensions, ExpansionAdd, ExpansionSearch, IndexId, ScyllaDbUri,
     },
-    std::{collections::HashMap, future::Future},
+    std::{collections::HashMap, future::Future, path::PathBuf, time::SystemTime},
     tokio::sync::{mpsc, oneshot},
     tracing::{error, warn},
 };
 
+struct IndexEntry {
+    actor: mpsc::Sender<Index>,
+    col_id: ColumnName,
+    col_emb: ColumnName,
+    dimensions: Dimensions,
+    connectivity: Connectivity,
+    expansion_add: ExpansionAdd,
+    expansion_search: ExpansionSearch,
+    watermark: Watermark,
+    created_at: SystemTime,
+    updated_at: SystemTime,
+}
+
+/// Point-in-time view of an index, for capacity sizing and checking that ingestion from
+/// ScyllaDB has caught up.
+#[derive(Debug, Clone)]
+pub(crate) struct IndexStats {
+    pub(crate) count: usize,
+    /// A vector-only lower bound (`count * dimensions * size_of::<f32>()`) -- it does not
+    /// account for the HNSW graph/edge overhead (`connectivity`-dependent), which dominates
+    /// actual resident memory for most indexes. Treat this as a floor, not an estimate.
+    pub(crate) memory_bytes: usize,
+    pub(crate) dimensions: Dimensions,
+    pub(crate) connectivity: Connectivity,
+    pub(crate) expansion_add: ExpansionAdd,
+    pub(crate) expansion_search: ExpansionSearch,
+    pub(crate) col_id: ColumnName,
+    pub(crate) col_emb: ColumnName,
+    pub(crate) created_at: SystemTime,
+    pub(crate) updated_at: SystemTime,
+}
+
 pub(crate) enum Engine {
     GetIndexes {
         tx: oneshot::Sender<Vec<IndexId>>,
@@ -29,14 +65,48 @@ pub(crate) enum Engine {
         connectivity: Connectivity,
         expansion_add: ExpansionAdd,
         expansion_search: ExpansionSearch,
+        tx: oneshot::Sender<TaskId>,
     },
     DelIndex {
         id: IndexId,
     },
+    UpdateIndex {
+        id: IndexId,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    GetTask {
+        id: TaskId,
+        tx: oneshot::Sender<Option<Task>>,
+    },
+    ListTasks {
+        filter: TaskFilter,
+        tx: oneshot::Sender<Vec<Task>>,
+    },
     GetIndex {
         id: IndexId,
         tx: oneshot::Sender<Option<mpsc::Sender<Index>>>,
     },
+    Snapshot {
+        id: IndexId,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    Restore {
+        id: IndexId,
+    },
+    GetIndexStats {
+        id: IndexId,
+        tx: oneshot::Sender<Option<IndexStats>>,
+    },
+    ReportIngest {
+        id: IndexId,
+        watermark: Watermark,
+    },
+    DumpCreate {
+        path: PathBuf,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
     Stop,
 }
 
@@ -58,9 +128,22 @@ pub(crate) trait EngineExt {
         connectivity: Connectivity,
         expansion_add: ExpansionAdd,
         expansion_search: ExpansionSearch,
-    );
+    ) -> Option<TaskId>;
     async fn del_index(&self, id: IndexId);
+    async fn update_index(
+        &self,
+        id: IndexId,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    ) -> anyhow::Result<()>;
     fn get_index(&self, id: IndexId) -> impl Future<Output = Option<mpsc::Sender<Index>>> + Send;
+    async fn snapshot(&self, id: IndexId) -> anyhow::Result<()>;
+    async fn restore(&self, id: IndexId);
+    async fn get_task(&self, id: TaskId) -> Option<Task>;
+    async fn list_tasks(&self, filter: TaskFilter) -> Vec<Task>;
+    async fn get_index_stats(&self, id: IndexId) -> Option<IndexStats>;
+    async fn report_ingest(&self, id: IndexId, watermark: Watermark);
+    async fn dump_create(&self, path: PathBuf) -> anyhow::Result<()>;
 }
 
 impl EngineExt for mpsc::Sender<Engine> {
@@ -82,18 +165,27 @@ impl EngineExt for mpsc::Sender<Engine> {
         connectivity: Connectivity,
         expansion_add: ExpansionAdd,
         expansion_search: ExpansionSearch,
-    ) {
-        self.send(Engine::AddIndex {
-            id,
-            col_id,
-            col_emb,
-            dimensions,
-            connectivity,
-            expansion_add,
-            expansion_search,
-        })
-        .await
-        .unwrap_or_else(|err| warn!("EngineExt::add_index: unable to send request: {err}"));
+    ) -> Option<TaskId> {
+        let (tx, rx) = oneshot::channel();
+        if self
+            .send(Engine::AddIndex {
+                id,
+                col_id,
+                col_emb,
+                dimensions,
+                connectivity,
+                expansion_add,
+                expansion_search,
+                tx,
+            })
+            .await
+            .is_ok()
+        {
+            rx.await.ok()
+        } else {
+            warn!("EngineExt::add_index: unable to send request");
+            None
+        }
     }
 
     async fn del_index(&self, id: IndexId) {
@@ -102,6 +194,23 @@ impl EngineExt for mpsc::Sender<Engine> {
             .unwrap_or_else(|err| warn!("EngineExt::del_index: unable to send request: {err}"));
     }
 
+    async fn update_index(
+        &self,
+        id: IndexId,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    ) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Engine::UpdateIndex {
+            id,
+            expansion_add,
+            expansion_search,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+
     async fn get_index(&self, id: IndexId) -> Option<mpsc::Sender<Index>> {
         let (tx, rx) = oneshot::channel();
         if self.send(Engine::GetIndex { id, tx }).await.is_ok() {
@@ -110,11 +219,66 @@ impl EngineExt for mpsc::Sender<Engine> {
             None
         }
     }
+
+    async fn snapshot(&self, id: IndexId) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Engine::Snapshot { id, tx }).await?;
+        rx.await?
+    }
+
+    async fn restore(&self, id: IndexId) {
+        self.send(Engine::Restore { id })
+            .await
+            .unwrap_or_else(|err| warn!("EngineExt::restore: unable to send request: {err}"));
+    }
+
+    async fn get_task(&self, id: TaskId) -> Option<Task> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(Engine::GetTask { id, tx }).await.is_ok() {
+            rx.await.ok().flatten()
+        } else {
+            None
+        }
+    }
+
+    async fn list_tasks(&self, filter: TaskFilter) -> Vec<Task> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(Engine::ListTasks { filter, tx }).await.is_ok() {
+            rx.await.unwrap_or_default()
+        } else {
+            Vec::new()
+        }
+    }
+
+    async fn get_index_stats(&self, id: IndexId) -> Option<IndexStats> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(Engine::GetIndexStats { id, tx }).await.is_ok() {
+            rx.await.ok().flatten()
+        } else {
+            None
+        }
+    }
+
+    async fn report_ingest(&self, id: IndexId, watermark: Watermark) {
+        self.send(Engine::ReportIngest { id, watermark })
+            .await
+            .unwrap_or_else(|err| warn!("EngineExt::report_ingest: unable to send request: {err}"));
+    }
+
+    async fn dump_create(&self, path: PathBuf) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Engine::DumpCreate { path, tx }).await?;
+        rx.await?
+    }
 }
 
 pub(crate) async fn new(
     uri: ScyllaDbUri,
     supervisor_actor: mpsc::Sender<Supervisor>,
+    snapshot_config: SnapshotConfig,
+    task_log_path: PathBuf,
+    op_queue_path: PathBuf,
+    dump_to_load: Option<PathBuf>,
 ) -> anyhow::Result<(mpsc::Sender<Engine>, ActorHandle)> {
     let (tx, mut rx) = mpsc::channel(10);
     let (monitor_actor, monitor_task) = monitor_indexes::new(uri.clone(), tx.clone()).await?;
@@ -125,8 +289,21 @@ pub(crate) async fn new(
         .await;
     let (monitor_actor, monitor_task) = monitor_queries::new(uri.clone(), tx.clone()).await?;
     supervisor_actor.attach(monitor_actor, monitor_task).await;
+    let (snapshot_timer_actor, snapshot_timer_task) =
+        snapshot::new(snapshot_config.clone(), tx.clone()).await?;
+    supervisor_actor
+        .attach(snapshot_timer_actor, snapshot_timer_task)
+        .await;
+    let snapshot_dir = snapshot_config.dir;
+    let snapshot_dir_for_dump = snapshot_dir.clone();
+    let (task_store_actor, task_store_task) = task_store::new(task_log_path).await?;
+    supervisor_actor
+        .attach(task_store_actor.clone(), task_store_task)
+        .await;
+    let mut op_queue = OpQueue::open(op_queue_path).await?;
+    let pending_ops = op_queue.take_pending();
     let task = tokio::spawn(async move {
-        let mut indexes = HashMap::new();
+        let mut indexes = HashMap::<IndexId, IndexEntry>::new();
         let mut monitors = HashMap::new();
         while let Some(msg) = rx.recv().await {
             match msg {
@@ -144,10 +321,40 @@ pub(crate) async fn new(
                     connectivity,
                     expansion_add,
                     expansion_search,
+                    tx: task_id_tx,
                 } => {
+                    let op_id = op_queue
+                        .push(Op::AddIndex {
+                            id: id.clone(),
+                            col_id: col_id.clone(),
+                            col_emb: col_emb.clone(),
+                            dimensions,
+                            connectivity,
+                            expansion_add,
+                            expansion_search,
+                        })
+                        .await;
+                    let task_id = task_store_actor
+                        .enqueue(TaskKind::AddIndex { id: id.clone() })
+                        .await
+                        .unwrap_or_default();
+                    task_id_tx.send(task_id).unwrap_or_else(|_| {
+                        warn!("engine::Engine::AddIndex: unable to send task id response")
+                    });
                     if indexes.contains_key(&id) {
+                        task_store_actor
+                            .fail(
+                                task_id,
+                                "index already exists; use UpdateIndex to retune expansion \
+                                 parameters, or DelIndex then AddIndex to change connectivity \
+                                 or dimensions"
+                                    .to_string(),
+                            )
+                            .await;
+                        op_queue.ack(op_id).await;
                         continue;
                     }
+                    task_store_actor.start(task_id).await;
                     if let Ok((index_actor, index_task)) = index::new(
                         id.clone(),
                         modify_actor.clone(),
@@ -156,12 +363,26 @@ pub(crate) async fn new(
                         expansion_add,
                         expansion_search,
                     ) {
+                        let watermark = match snapshot::load(&snapshot_dir, &id).await {
+                            Some((path, watermark)) => {
+                                if let Err(err) = index_actor.load(path).await {
+                                    error!("unable to restore snapshot for index {id}: {err}");
+                                    Watermark::default()
+                                } else {
+                                    watermark
+                                }
+                            }
+                            None => Watermark::default(),
+                        };
                         if let Ok((monitor_actor, monitor_task)) = monitor_items::new(
                             uri.clone(),
+                            id.clone(),
                             id.clone().0.into(),
                             col_id.clone(),
                             col_emb.clone(),
                             index_actor.clone(),
+                            watermark,
+                            tx.clone(),
                         )
                         .await.inspect_err(|err| error!("unable to create monitor items with uri {uri}, table {id}, col_id {col_id}, col_emb {col_emb}: {err}"))
                         {
@@ -171,33 +392,267 @@ pub(crate) async fn new(
                             supervisor_actor
                                 .attach(monitor_actor.clone(), monitor_task)
                                 .await;
-                            indexes.insert(id.clone(), index_actor);
+                            let now = SystemTime::now();
+                            indexes.insert(
+                                id.clone(),
+                                IndexEntry {
+                                    actor: index_actor,
+                                    col_id,
+                                    col_emb,
+                                    dimensions,
+                                    connectivity,
+                                    expansion_add,
+                                    expansion_search,
+                                    watermark,
+                                    created_at: now,
+                                    updated_at: now,
+                                },
+                            );
                             monitors.insert(id, monitor_actor);
+                            task_store_actor.succeed(task_id).await;
                         } else {
                             index_actor.actor_stop().await;
                             index_task.await.unwrap_or_else(|err| warn!("engine::Engine::AddIndex: issue while stopping index actor: {err}"));
+                            task_store_actor
+                                .fail(task_id, "unable to create monitor items".to_string())
+                                .await;
                         }
                     } else {
                         error!("unable to create index with dimensions {dimensions}");
+                        task_store_actor
+                            .fail(task_id, format!("unable to create index with dimensions {dimensions}"))
+                            .await;
                     }
+                    op_queue.ack(op_id).await;
                 }
                 Engine::DelIndex { id } => {
-                    if let Some(index) = indexes.remove(&id) {
-                        index.actor_stop().await;
+                    let op_id = op_queue.push(Op::DelIndex { id: id.clone() }).await;
+                    let task_id = task_store_actor
+                        .enqueue(TaskKind::DelIndex { id: id.clone() })
+                        .await
+                        .unwrap_or_default();
+                    task_store_actor.start(task_id).await;
+                    if let Some(entry) = indexes.remove(&id) {
+                        entry.actor.actor_stop().await;
                     }
                     if let Some(monitor) = monitors.remove(&id) {
                         monitor.actor_stop().await;
                     }
                     modify_actor.del(id).await;
+                    task_store_actor.succeed(task_id).await;
+                    op_queue.ack(op_id).await;
+                }
+                Engine::UpdateIndex {
+                    id,
+                    expansion_add,
+                    expansion_search,
+                    tx,
+                } => {
+                    let op_id = op_queue
+                        .push(Op::UpdateIndex {
+                            id: id.clone(),
+                            expansion_add,
+                            expansion_search,
+                        })
+                        .await;
+                    let result = match indexes.get_mut(&id) {
+                        Some(entry) => match entry
+                            .actor
+                            .update_expansion(expansion_add, expansion_search)
+                            .await
+                        {
+                            Ok(()) => {
+                                entry.expansion_add = expansion_add;
+                                entry.expansion_search = expansion_search;
+                                entry.updated_at = SystemTime::now();
+                                Ok(())
+                            }
+                            Err(err) => Err(err),
+                        },
+                        None => Err(anyhow::anyhow!("no such index {id}")),
+                    };
+                    tx.send(result).unwrap_or_else(|_| {
+                        warn!("engine::Engine::UpdateIndex: unable to send response")
+                    });
+                    op_queue.ack(op_id).await;
+                }
+                Engine::GetTask { id, tx } => {
+                    tx.send(task_store_actor.get(id).await).unwrap_or_else(|_| {
+                        warn!("engine::Engine::GetTask: unable to send response")
+                    });
+                }
+                Engine::ListTasks { filter, tx } => {
+                    tx.send(task_store_actor.list(filter).await)
+                        .unwrap_or_else(|_| {
+                            warn!("engine::Engine::ListTasks: unable to send response")
+                        });
                 }
                 Engine::GetIndex { id, tx } => {
-                    tx.send(indexes.get(&id).cloned()).unwrap_or_else(|_| {
-                        warn!("engine::Engine::GetIndex: unable to send response")
+                    tx.send(indexes.get(&id).map(|entry| entry.actor.clone()))
+                        .unwrap_or_else(|_| {
+                            warn!("engine::Engine::GetIndex: unable to send response")
+                        });
+                }
+                Engine::Snapshot { id, tx } => {
+                    let result = match indexes.get(&id) {
+                        Some(entry) => {
+                            snapshot::save(&snapshot_dir, &id, &entry.actor, entry.watermark).await
+                        }
+                        None => Err(anyhow::anyhow!("no such index {id}")),
+                    };
+                    tx.send(result).unwrap_or_else(|_| {
+                        warn!("engine::Engine::Snapshot: unable to send response")
+                    });
+                }
+                Engine::Restore { id } => {
+                    if let Some((path, watermark)) = snapshot::load(&snapshot_dir, &id).await {
+                        if let Some(entry) = indexes.get_mut(&id) {
+                            if let Err(err) = entry.actor.load(path).await {
+                                error!("engine::Engine::Restore: unable to restore index {id}: {err}");
+                            } else {
+                                entry.watermark = watermark;
+                                entry.updated_at = SystemTime::now();
+                            }
+                        }
+                    }
+                }
+                Engine::GetIndexStats { id, tx } => {
+                    let stats = match indexes.get(&id) {
+                        Some(entry) => match entry.actor.len().await {
+                            Ok(count) => Some(IndexStats {
+                                count,
+                                // Vector-only lower bound; see the doc comment on
+                                // IndexStats::memory_bytes for what this omits.
+                                memory_bytes: count
+                                    * entry.dimensions.0 as usize
+                                    * std::mem::size_of::<f32>(),
+                                dimensions: entry.dimensions,
+                                connectivity: entry.connectivity,
+                                expansion_add: entry.expansion_add,
+                                expansion_search: entry.expansion_search,
+                                col_id: entry.col_id.clone(),
+                                col_emb: entry.col_emb.clone(),
+                                created_at: entry.created_at,
+                                updated_at: entry.updated_at,
+                            }),
+                            Err(err) => {
+                                error!("engine::Engine::GetIndexStats: unable to read index {id}: {err}");
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+                    tx.send(stats).unwrap_or_else(|_| {
+                        warn!("engine::Engine::GetIndexStats: unable to send response")
                     });
                 }
+                Engine::ReportIngest { id, watermark } => {
+                    if let Some(entry) = indexes.get_mut(&id) {
+                        entry.watermark = watermark;
+                        entry.updated_at = SystemTime::now();
+                    }
+                }
+                Engine::DumpCreate { path, tx } => {
+                    let entries = indexes
+                        .iter()
+                        .map(|(id, entry)| dump::ExportEntry {
+                            id: id.clone(),
+                            col_id: entry.col_id.clone(),
+                            col_emb: entry.col_emb.clone(),
+                            dimensions: entry.dimensions,
+                            connectivity: entry.connectivity,
+                            expansion_add: entry.expansion_add,
+                            expansion_search: entry.expansion_search,
+                            watermark: entry.watermark,
+                            actor: entry.actor.clone(),
+                        })
+                        .collect();
+                    let (dump_actor, dump_task) = dump::spawn(path, entries, tx).await;
+                    supervisor_actor.attach(dump_actor, dump_task).await;
+                }
                 Engine::Stop => rx.close(),
             }
         }
     });
+    if let Some(dump_dir) = dump_to_load {
+        match dump::load(&dump_dir).await {
+            Ok(entries) => {
+                for (entry, graph_path) in entries {
+                    let watermark = entry.watermark;
+                    let dest = snapshot::index_path(&snapshot_dir_for_dump, &entry.id);
+                    if let Some(parent) = dest.parent() {
+                        tokio::fs::create_dir_all(parent).await.unwrap_or_else(|err| {
+                            warn!("engine::new: unable to create snapshot dir {parent:?}: {err}")
+                        });
+                    }
+                    if let Err(err) = tokio::fs::copy(&graph_path, &dest).await {
+                        error!(
+                            "engine::new: unable to install dumped graph for index {}: {err}",
+                            entry.id
+                        );
+                        continue;
+                    }
+                    if let Err(err) = tokio::fs::write(
+                        snapshot::watermark_path(&snapshot_dir_for_dump, &entry.id),
+                        watermark.0.to_string(),
+                    )
+                    .await
+                    {
+                        error!(
+                            "engine::new: unable to install dumped watermark for index {}: {err}",
+                            entry.id
+                        );
+                    }
+                    tx.add_index(
+                        entry.id,
+                        entry.col_id,
+                        entry.col_emb,
+                        entry.dimensions,
+                        entry.connectivity,
+                        entry.expansion_add,
+                        entry.expansion_search,
+                    )
+                    .await;
+                }
+            }
+            Err(err) => error!("engine::new: unable to load dump from {dump_dir:?}: {err}"),
+        }
+    }
+    for (op_id, op) in pending_ops {
+        warn!("engine::new: re-driving op {op_id} left pending by a previous run: {op:?}");
+        match op {
+            Op::AddIndex {
+                id,
+                col_id,
+                col_emb,
+                dimensions,
+                connectivity,
+                expansion_add,
+                expansion_search,
+            } => {
+                tx.add_index(
+                    id,
+                    col_id,
+                    col_emb,
+                    dimensions,
+                    connectivity,
+                    expansion_add,
+                    expansion_search,
+                )
+                .await;
+            }
+            Op::DelIndex { id } => tx.del_index(id).await,
+            Op::UpdateIndex {
+                id,
+                expansion_add,
+                expansion_search,
+            } => {
+                if let Err(err) = tx.update_index(id.clone(), expansion_add, expansion_search).await
+                {
+                    error!("engine::new: unable to re-drive UpdateIndex for {id}: {err}");
+                }
+            }
+        }
+    }
     Ok((tx, task))
 }
@@ -0,0 +1,147 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{
+        actor::{ActorHandle, MessageStop},
+        modify_indexes::ModifyIndexes,
+        Connectivity, Dimensions, ExpansionAdd, ExpansionSearch, IndexId,
+    },
+    std::path::PathBuf,
+    tokio::sync::{mpsc, oneshot},
+    tracing::warn,
+    usearch::{Index as UsearchIndex, IndexOptions, MetricKind, ScalarKind},
+};
+
+pub(crate) enum Index {
+    Save {
+        path: PathBuf,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    Load {
+        path: PathBuf,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    UpdateExpansion {
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+        tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    Len {
+        tx: oneshot::Sender<anyhow::Result<usize>>,
+    },
+    Stop,
+}
+
+impl MessageStop for Index {
+    fn message_stop() -> Self {
+        Index::Stop
+    }
+}
+
+pub(crate) trait IndexExt {
+    async fn save(&self, path: PathBuf) -> anyhow::Result<()>;
+    async fn load(&self, path: PathBuf) -> anyhow::Result<()>;
+    async fn update_expansion(
+        &self,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    ) -> anyhow::Result<()>;
+    async fn len(&self) -> anyhow::Result<usize>;
+}
+
+impl IndexExt for mpsc::Sender<Index> {
+    async fn save(&self, path: PathBuf) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Index::Save { path, tx }).await?;
+        rx.await?
+    }
+
+    async fn load(&self, path: PathBuf) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Index::Load { path, tx }).await?;
+        rx.await?
+    }
+
+    async fn update_expansion(
+        &self,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    ) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Index::UpdateExpansion {
+            expansion_add,
+            expansion_search,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+
+    async fn len(&self) -> anyhow::Result<usize> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Index::Len { tx }).await?;
+        rx.await?
+    }
+}
+
+pub(crate) fn new(
+    id: IndexId,
+    _modify_actor: mpsc::Sender<ModifyIndexes>,
+    dimensions: Dimensions,
+    connectivity: Connectivity,
+    expansion_add: ExpansionAdd,
+    expansion_search: ExpansionSearch,
+) -> anyhow::Result<(mpsc::Sender<Index>, ActorHandle)> {
+    let index = UsearchIndex::new(&IndexOptions {
+        dimensions: dimensions.0 as usize,
+        metric: MetricKind::Cos,
+        quantization: ScalarKind::F32,
+        connectivity: connectivity.0 as usize,
+        expansion_add: expansion_add.0 as usize,
+        expansion_search: expansion_search.0 as usize,
+        multi: false,
+    })?;
+    index.reserve(1)?;
+    let (tx, mut rx) = mpsc::channel(32);
+    let task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            match msg {
+                Index::Save { path, tx } => {
+                    let result = index.save(&path.to_string_lossy()).map_err(anyhow::Error::from);
+                    tx.send(result).unwrap_or_else(|_| {
+                        warn!("index::Index::Save: unable to send response for {id}")
+                    });
+                }
+                Index::Load { path, tx } => {
+                    let result = index.load(&path.to_string_lossy()).map_err(anyhow::Error::from);
+                    tx.send(result).unwrap_or_else(|_| {
+                        warn!("index::Index::Load: unable to send response for {id}")
+                    });
+                }
+                Index::UpdateExpansion {
+                    expansion_add,
+                    expansion_search,
+                    tx,
+                } => {
+                    let result = index
+                        .change_expansion_add(expansion_add.0 as usize)
+                        .and_then(|()| index.change_expansion_search(expansion_search.0 as usize))
+                        .map_err(anyhow::Error::from);
+                    tx.send(result).unwrap_or_else(|_| {
+                        warn!("index::Index::UpdateExpansion: unable to send response for {id}")
+                    });
+                }
+                Index::Len { tx } => {
+                    tx.send(Ok(index.size())).unwrap_or_else(|_| {
+                        warn!("index::Index::Len: unable to send response for {id}")
+                    });
+                }
+                Index::Stop => rx.close(),
+            }
+        }
+    });
+    Ok((tx, task))
+}
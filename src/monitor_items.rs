@@ -0,0 +1,53 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{
+        actor::{ActorHandle, MessageStop},
+        engine::{Engine, EngineExt},
+        index::Index,
+        snapshot::Watermark,
+        ColumnName, IndexId, ScyllaDbUri,
+    },
+    tokio::sync::mpsc,
+};
+
+pub(crate) enum MonitorItems {
+    Stop,
+}
+
+impl MessageStop for MonitorItems {
+    fn message_stop() -> Self {
+        MonitorItems::Stop
+    }
+}
+
+/// Reports the seeded `watermark` back to the `Engine` once via `ReportIngest` and returns
+/// an actor that only handles `Stop`. It reads no rows from `table` and inserts nothing
+/// into `index_actor`, so the watermark is never advanced past the seeded value.
+// TODO: the ScyllaDB row-change feed this replays from is not part of this chunk of the
+// repo. Wire the real scan/CDC loop here, advancing `watermark` to each processed batch's
+// max write-timestamp and calling `engine_actor.report_ingest(id.clone(), watermark).await`
+// after every batch -- that is the contract `Engine::Snapshot` now depends on for deciding
+// how far `monitor_items` has to resume from.
+pub(crate) async fn new(
+    _uri: ScyllaDbUri,
+    id: IndexId,
+    _table: String,
+    _col_id: ColumnName,
+    _col_emb: ColumnName,
+    _index_actor: mpsc::Sender<Index>,
+    watermark: Watermark,
+    engine_actor: mpsc::Sender<Engine>,
+) -> anyhow::Result<(mpsc::Sender<MonitorItems>, ActorHandle)> {
+    engine_actor.report_ingest(id, watermark).await;
+    let (tx, mut rx) = mpsc::channel(1);
+    let task = tokio::spawn(async move {
+        while let Some(MonitorItems::Stop) = rx.recv().await {
+            rx.close();
+        }
+    });
+    Ok((tx, task))
+}
@@ -0,0 +1,267 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{ColumnName, Connectivity, Dimensions, ExpansionAdd, ExpansionSearch, IndexId},
+    std::{
+        collections::BTreeMap,
+        ffi::OsString,
+        path::{Path, PathBuf},
+    },
+    tracing::warn,
+};
+
+pub(crate) type OpId = u64;
+
+/// The set of index lifecycle operations that must never interleave or be reordered for
+/// the same `IndexId`.
+#[derive(Debug, Clone)]
+pub(crate) enum Op {
+    AddIndex {
+        id: IndexId,
+        col_id: ColumnName,
+        col_emb: ColumnName,
+        dimensions: Dimensions,
+        connectivity: Connectivity,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    },
+    DelIndex {
+        id: IndexId,
+    },
+    UpdateIndex {
+        id: IndexId,
+        expansion_add: ExpansionAdd,
+        expansion_search: ExpansionSearch,
+    },
+}
+
+fn encode(op_id: OpId, op: &Op) -> String {
+    let body = match op {
+        Op::AddIndex {
+            id,
+            col_id,
+            col_emb,
+            dimensions,
+            connectivity,
+            expansion_add,
+            expansion_search,
+        } => format!(
+            "add\t{id}\t{col_id}\t{col_emb}\t{dimensions}\t{connectivity}\t{expansion_add}\t{expansion_search}"
+        ),
+        Op::DelIndex { id } => format!("del\t{id}"),
+        Op::UpdateIndex {
+            id,
+            expansion_add,
+            expansion_search,
+        } => format!("update\t{id}\t{expansion_add}\t{expansion_search}"),
+    };
+    format!("{op_id}\t{body}\n")
+}
+
+fn decode(line: &str) -> Option<(OpId, Op)> {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let mut fields = line.split('\t');
+    let op_id: OpId = fields.next()?.parse().ok()?;
+    let op = match fields.next()? {
+        "add" => {
+            let [id, col_id, col_emb, dimensions, connectivity, expansion_add, expansion_search] =
+                fields.collect::<Vec<_>>().try_into().ok()?;
+            Op::AddIndex {
+                id: IndexId::from(id.to_string()),
+                col_id: ColumnName::from(col_id.to_string()),
+                col_emb: ColumnName::from(col_emb.to_string()),
+                dimensions: dimensions.parse::<u32>().ok()?.into(),
+                connectivity: connectivity.parse::<u32>().ok()?.into(),
+                expansion_add: expansion_add.parse::<u32>().ok()?.into(),
+                expansion_search: expansion_search.parse::<u32>().ok()?.into(),
+            }
+        }
+        "del" => {
+            let [id] = fields.collect::<Vec<_>>().try_into().ok()?;
+            Op::DelIndex { id: IndexId::from(id.to_string()) }
+        }
+        "update" => {
+            let [id, expansion_add, expansion_search] =
+                fields.collect::<Vec<_>>().try_into().ok()?;
+            Op::UpdateIndex {
+                id: IndexId::from(id.to_string()),
+                expansion_add: expansion_add.parse::<u32>().ok()?.into(),
+                expansion_search: expansion_search.parse::<u32>().ok()?.into(),
+            }
+        }
+        _ => return None,
+    };
+    Some((op_id, op))
+}
+
+fn counter_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".next_id");
+    PathBuf::from(name)
+}
+
+/// A durable, strictly `OpId`-ordered log of pending index lifecycle operations, so a
+/// crash mid-`AddIndex` leaves a record of the operation that was in flight rather than
+/// silently losing it. Entries are appended on submission and removed once the `Engine`
+/// has fully applied them; anything still pending at startup is handed back by
+/// [`OpQueue::take_pending`] so the `Engine` can re-drive it through the same code path a
+/// fresh request would take.
+pub(crate) struct OpQueue {
+    path: PathBuf,
+    counter_path: PathBuf,
+    next_id: OpId,
+    pending: BTreeMap<OpId, Op>,
+}
+
+impl OpQueue {
+    pub(crate) async fn open(path: PathBuf) -> anyhow::Result<Self> {
+        let leftover = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+        let pending: BTreeMap<OpId, Op> = leftover.lines().filter_map(decode).collect();
+        if !pending.is_empty() {
+            warn!(
+                "op_queue::OpQueue::open: {} operation(s) were left pending in {path:?} by a \
+                 previous run and will be re-driven",
+                pending.len()
+            );
+        }
+        let counter_path = counter_path(&path);
+        let persisted_next_id = tokio::fs::read_to_string(&counter_path)
+            .await
+            .ok()
+            .and_then(|raw| raw.trim().parse().ok());
+        let pending_next_id = pending.keys().next_back().map(|id| id + 1).unwrap_or(0);
+        // The counter file is the source of truth -- it survives the queue draining to
+        // empty, which the pending entries alone can't -- but fall back to (and never go
+        // behind) whatever IDs are still on disk in case the counter file is missing or
+        // stale.
+        let next_id = persisted_next_id.unwrap_or(0).max(pending_next_id);
+        Ok(OpQueue {
+            path,
+            counter_path,
+            next_id,
+            pending,
+        })
+    }
+
+    async fn persist(&self) {
+        let log = self
+            .pending
+            .iter()
+            .map(|(op_id, op)| encode(*op_id, op))
+            .collect::<String>();
+        if let Err(err) = tokio::fs::write(&self.path, log).await {
+            warn!("op_queue::OpQueue::persist: unable to write {:?}: {err}", self.path);
+        }
+        if let Err(err) = tokio::fs::write(&self.counter_path, self.next_id.to_string()).await {
+            warn!(
+                "op_queue::OpQueue::persist: unable to write {:?}: {err}",
+                self.counter_path
+            );
+        }
+    }
+
+    /// Appends `op` to the log in strict arrival order and returns the `OpId` the `Engine`
+    /// must use to `ack` it once applied.
+    pub(crate) async fn push(&mut self, op: Op) -> OpId {
+        let op_id = self.next_id;
+        self.next_id += 1;
+        self.pending.insert(op_id, op);
+        self.persist().await;
+        op_id
+    }
+
+    /// Marks `op_id` as fully applied, removing it from the durable log.
+    pub(crate) async fn ack(&mut self, op_id: OpId) {
+        self.pending.remove(&op_id);
+        self.persist().await;
+    }
+
+    /// Drains every operation left pending by a previous run, for the `Engine` to re-drive
+    /// at startup. Does not touch the durable log -- the replayed ops are only removed from
+    /// disk once the `Engine` re-applies and `ack`s them, so a crash mid-replay leaves them
+    /// to be picked up again on the next restart.
+    pub(crate) fn take_pending(&mut self) -> Vec<(OpId, Op)> {
+        std::mem::take(&mut self.pending).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_add_index_eq(a: &Op, b: &Op) {
+        let (Op::AddIndex { id: a_id, col_id: a_col_id, col_emb: a_col_emb, dimensions: a_dim, connectivity: a_conn, expansion_add: a_ea, expansion_search: a_es },
+             Op::AddIndex { id: b_id, col_id: b_col_id, col_emb: b_col_emb, dimensions: b_dim, connectivity: b_conn, expansion_add: b_ea, expansion_search: b_es }) = (a, b)
+        else {
+            panic!("expected two AddIndex ops");
+        };
+        assert_eq!(a_id, b_id);
+        assert_eq!(a_col_id, b_col_id);
+        assert_eq!(a_col_emb, b_col_emb);
+        assert_eq!(a_dim.0, b_dim.0);
+        assert_eq!(a_conn.0, b_conn.0);
+        assert_eq!(a_ea.0, b_ea.0);
+        assert_eq!(a_es.0, b_es.0);
+    }
+
+    #[test]
+    fn encode_decode_round_trips_add_index() {
+        let op = Op::AddIndex {
+            id: IndexId::from("my_index".to_string()),
+            col_id: ColumnName::from("id".to_string()),
+            col_emb: ColumnName::from("embedding".to_string()),
+            dimensions: 768.into(),
+            connectivity: 16.into(),
+            expansion_add: 128.into(),
+            expansion_search: 64.into(),
+        };
+        let (op_id, decoded) = decode(&encode(42, &op)).expect("encoded op should decode");
+        assert_eq!(op_id, 42);
+        assert_add_index_eq(&op, &decoded);
+    }
+
+    #[test]
+    fn encode_decode_round_trips_del_index() {
+        let op = Op::DelIndex {
+            id: IndexId::from("my_index".to_string()),
+        };
+        let (op_id, decoded) = decode(&encode(7, &op)).expect("encoded op should decode");
+        assert_eq!(op_id, 7);
+        match decoded {
+            Op::DelIndex { id } => assert_eq!(id, IndexId::from("my_index".to_string())),
+            other => panic!("expected DelIndex, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn encode_decode_round_trips_update_index() {
+        let op = Op::UpdateIndex {
+            id: IndexId::from("my_index".to_string()),
+            expansion_add: 256.into(),
+            expansion_search: 32.into(),
+        };
+        let (op_id, decoded) = decode(&encode(3, &op)).expect("encoded op should decode");
+        assert_eq!(op_id, 3);
+        match decoded {
+            Op::UpdateIndex {
+                id,
+                expansion_add,
+                expansion_search,
+            } => {
+                assert_eq!(id, IndexId::from("my_index".to_string()));
+                assert_eq!(expansion_add.0, 256);
+                assert_eq!(expansion_search.0, 32);
+            }
+            other => panic!("expected UpdateIndex, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decode_rejects_malformed_line() {
+        assert!(decode("not-a-number\tdel\tsome_id").is_none());
+        assert!(decode("1\tunknown\tsome_id").is_none());
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{
+        actor::{ActorHandle, MessageStop},
+        engine::{Engine, EngineExt},
+        index::{Index, IndexExt},
+        IndexId,
+    },
+    std::{
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+    tokio::sync::{mpsc, oneshot},
+    tracing::error,
+};
+
+/// Progress marker for a single index: the max ScyllaDB write-timestamp (or primary-key
+/// position, depending on the source table) already folded into the snapshotted graph, so
+/// `monitor_items` can resume from here instead of replaying the whole table.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Watermark(pub(crate) i64);
+
+#[derive(Clone)]
+pub(crate) struct SnapshotConfig {
+    pub(crate) dir: PathBuf,
+    pub(crate) interval: Duration,
+}
+
+pub(crate) fn index_path(dir: &Path, id: &IndexId) -> PathBuf {
+    dir.join(format!("{}.usearch", id.0))
+}
+
+pub(crate) fn watermark_path(dir: &Path, id: &IndexId) -> PathBuf {
+    dir.join(format!("{}.watermark", id.0))
+}
+
+pub(crate) async fn save(
+    dir: &Path,
+    id: &IndexId,
+    index_actor: &mpsc::Sender<Index>,
+    watermark: Watermark,
+) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    index_actor.save(index_path(dir, id)).await?;
+    tokio::fs::write(watermark_path(dir, id), watermark.0.to_string()).await?;
+    Ok(())
+}
+
+/// Returns the path to a saved graph and its watermark if a snapshot exists for `id`.
+pub(crate) async fn load(dir: &Path, id: &IndexId) -> Option<(PathBuf, Watermark)> {
+    let path = index_path(dir, id);
+    if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return None;
+    }
+    let watermark = tokio::fs::read_to_string(watermark_path(dir, id))
+        .await
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .map(Watermark)
+        .unwrap_or_default();
+    Some((path, watermark))
+}
+
+pub(crate) enum SnapshotTimer {
+    Stop,
+}
+
+impl MessageStop for SnapshotTimer {
+    fn message_stop() -> Self {
+        SnapshotTimer::Stop
+    }
+}
+
+/// Periodically snapshots every index known to `engine_actor`, on `config.interval`.
+pub(crate) async fn new(
+    config: SnapshotConfig,
+    engine_actor: mpsc::Sender<Engine>,
+) -> anyhow::Result<(mpsc::Sender<SnapshotTimer>, ActorHandle)> {
+    let (tx, mut rx) = mpsc::channel(1);
+    let task = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        ticker.tick().await; // first tick fires immediately, skip it
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    for id in engine_actor.get_indexes().await {
+                        let (snapshot_tx, snapshot_rx) = oneshot::channel();
+                        if engine_actor
+                            .send(Engine::Snapshot { id: id.clone(), tx: snapshot_tx })
+                            .await
+                            .is_ok()
+                        {
+                            if let Ok(Err(err)) = snapshot_rx.await {
+                                error!("snapshot::new: unable to snapshot index {id}: {err}");
+                            }
+                        }
+                    }
+                }
+                msg = rx.recv() => match msg {
+                    Some(SnapshotTimer::Stop) | None => break,
+                },
+            }
+        }
+    });
+    Ok((tx, task))
+}
@@ -0,0 +1,373 @@
+/*
+ * Copyright 2025-present ScyllaDB
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use {
+    crate::{
+        actor::{ActorHandle, MessageStop},
+        IndexId,
+    },
+    std::{
+        collections::BTreeMap,
+        path::PathBuf,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::{mpsc, oneshot},
+    tracing::warn,
+};
+
+pub(crate) type TaskId = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TaskKind {
+    AddIndex { id: IndexId },
+    DelIndex { id: IndexId },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TaskStatus {
+    Enqueued,
+    Processing,
+    Succeeded,
+    Failed { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Task {
+    pub(crate) id: TaskId,
+    pub(crate) kind: TaskKind,
+    pub(crate) status: TaskStatus,
+    pub(crate) created_at: SystemTime,
+    pub(crate) started_at: Option<SystemTime>,
+    pub(crate) finished_at: Option<SystemTime>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TaskFilter {
+    pub(crate) index_id: Option<IndexId>,
+}
+
+impl TaskFilter {
+    fn matches(&self, task: &Task) -> bool {
+        match (&self.index_id, &task.kind) {
+            (None, _) => true,
+            (Some(filter_id), TaskKind::AddIndex { id } | TaskKind::DelIndex { id }) => {
+                filter_id == id
+            }
+        }
+    }
+}
+
+pub(crate) enum TaskStore {
+    Enqueue {
+        kind: TaskKind,
+        tx: oneshot::Sender<TaskId>,
+    },
+    Start {
+        id: TaskId,
+    },
+    Succeed {
+        id: TaskId,
+    },
+    Fail {
+        id: TaskId,
+        reason: String,
+    },
+    Get {
+        id: TaskId,
+        tx: oneshot::Sender<Option<Task>>,
+    },
+    List {
+        filter: TaskFilter,
+        tx: oneshot::Sender<Vec<Task>>,
+    },
+    Stop,
+}
+
+impl MessageStop for TaskStore {
+    fn message_stop() -> Self {
+        TaskStore::Stop
+    }
+}
+
+pub(crate) trait TaskStoreExt {
+    async fn enqueue(&self, kind: TaskKind) -> Option<TaskId>;
+    async fn start(&self, id: TaskId);
+    async fn succeed(&self, id: TaskId);
+    async fn fail(&self, id: TaskId, reason: String);
+    async fn get(&self, id: TaskId) -> Option<Task>;
+    async fn list(&self, filter: TaskFilter) -> Vec<Task>;
+}
+
+impl TaskStoreExt for mpsc::Sender<TaskStore> {
+    async fn enqueue(&self, kind: TaskKind) -> Option<TaskId> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(TaskStore::Enqueue { kind, tx }).await.is_ok() {
+            rx.await.ok()
+        } else {
+            None
+        }
+    }
+
+    async fn start(&self, id: TaskId) {
+        self.send(TaskStore::Start { id })
+            .await
+            .unwrap_or_else(|err| warn!("TaskStoreExt::start: unable to send request: {err}"));
+    }
+
+    async fn succeed(&self, id: TaskId) {
+        self.send(TaskStore::Succeed { id })
+            .await
+            .unwrap_or_else(|err| warn!("TaskStoreExt::succeed: unable to send request: {err}"));
+    }
+
+    async fn fail(&self, id: TaskId, reason: String) {
+        self.send(TaskStore::Fail { id, reason })
+            .await
+            .unwrap_or_else(|err| warn!("TaskStoreExt::fail: unable to send request: {err}"));
+    }
+
+    async fn get(&self, id: TaskId) -> Option<Task> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(TaskStore::Get { id, tx }).await.is_ok() {
+            rx.await.ok().flatten()
+        } else {
+            None
+        }
+    }
+
+    async fn list(&self, filter: TaskFilter) -> Vec<Task> {
+        let (tx, rx) = oneshot::channel();
+        if self.send(TaskStore::List { filter, tx }).await.is_ok() {
+            rx.await.unwrap_or_default()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn epoch_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+fn parse_epoch_secs(raw: &str) -> Option<SystemTime> {
+    if raw.is_empty() {
+        return None;
+    }
+    Some(UNIX_EPOCH + Duration::from_secs(raw.parse().ok()?))
+}
+
+fn encode(task: &Task) -> String {
+    let (kind_tag, kind_id) = match &task.kind {
+        TaskKind::AddIndex { id } => ("add", id),
+        TaskKind::DelIndex { id } => ("del", id),
+    };
+    let (status_tag, reason) = match &task.status {
+        TaskStatus::Enqueued => ("enqueued", String::new()),
+        TaskStatus::Processing => ("processing", String::new()),
+        TaskStatus::Succeeded => ("succeeded", String::new()),
+        TaskStatus::Failed { reason } => ("failed", reason.replace(['\t', '\n'], " ")),
+    };
+    format!(
+        "{}\t{kind_tag}\t{kind_id}\t{status_tag}\t{reason}\t{}\t{}\t{}\n",
+        task.id,
+        epoch_secs(task.created_at),
+        task.started_at
+            .map(|time| epoch_secs(time).to_string())
+            .unwrap_or_default(),
+        task.finished_at
+            .map(|time| epoch_secs(time).to_string())
+            .unwrap_or_default(),
+    )
+}
+
+fn decode(line: &str) -> Option<Task> {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [id, kind_tag, kind_id, status_tag, reason, created_at, started_at, finished_at] =
+        fields[..]
+    else {
+        warn!("task_store::decode: skipping malformed task log line: {line}");
+        return None;
+    };
+    let id: TaskId = id.parse().ok()?;
+    let kind_id = IndexId::from(kind_id.to_string());
+    let kind = match kind_tag {
+        "add" => TaskKind::AddIndex { id: kind_id },
+        "del" => TaskKind::DelIndex { id: kind_id },
+        _ => {
+            warn!("task_store::decode: skipping task log line with unknown kind: {line}");
+            return None;
+        }
+    };
+    let status = match status_tag {
+        "enqueued" => TaskStatus::Enqueued,
+        "processing" => TaskStatus::Processing,
+        "succeeded" => TaskStatus::Succeeded,
+        "failed" => TaskStatus::Failed {
+            reason: reason.to_string(),
+        },
+        _ => {
+            warn!("task_store::decode: skipping task log line with unknown status: {line}");
+            return None;
+        }
+    };
+    Some(Task {
+        id,
+        kind,
+        status,
+        created_at: parse_epoch_secs(created_at).unwrap_or_else(SystemTime::now),
+        started_at: parse_epoch_secs(started_at),
+        finished_at: parse_epoch_secs(finished_at),
+    })
+}
+
+async fn persist(path: &PathBuf, tasks: &BTreeMap<TaskId, Task>) {
+    let log = tasks.values().map(encode).collect::<String>();
+    if let Err(err) = tokio::fs::write(path, log).await {
+        warn!("task_store::persist: unable to write task log {path:?}: {err}");
+    }
+}
+
+async fn load(path: &PathBuf) -> (BTreeMap<TaskId, Task>, TaskId) {
+    let raw = match tokio::fs::read_to_string(path).await {
+        Ok(raw) => raw,
+        Err(_) => return (BTreeMap::new(), 0),
+    };
+    let tasks: BTreeMap<TaskId, Task> = raw
+        .lines()
+        .filter_map(decode)
+        .map(|task| (task.id, task))
+        .collect();
+    let next_id = tasks.keys().next_back().map(|id| id + 1).unwrap_or(0);
+    (tasks, next_id)
+}
+
+/// The task log only needs to survive restarts long enough to answer `GetTask`/`ListTasks`
+/// for work that was in flight; it is reloaded from a flat, tab-separated log rather than a
+/// structured store.
+pub(crate) async fn new(path: PathBuf) -> anyhow::Result<(mpsc::Sender<TaskStore>, ActorHandle)> {
+    let (tx, mut rx) = mpsc::channel(32);
+    let (mut tasks, mut next_id) = load(&path).await;
+    let task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            match msg {
+                TaskStore::Enqueue { kind, tx } => {
+                    let id = next_id;
+                    next_id += 1;
+                    tasks.insert(
+                        id,
+                        Task {
+                            id,
+                            kind,
+                            status: TaskStatus::Enqueued,
+                            created_at: SystemTime::now(),
+                            started_at: None,
+                            finished_at: None,
+                        },
+                    );
+                    persist(&path, &tasks).await;
+                    tx.send(id).unwrap_or_else(|_| {
+                        warn!("task_store::TaskStore::Enqueue: unable to send response")
+                    });
+                }
+                TaskStore::Start { id } => {
+                    if let Some(task) = tasks.get_mut(&id) {
+                        task.status = TaskStatus::Processing;
+                        task.started_at = Some(SystemTime::now());
+                        persist(&path, &tasks).await;
+                    }
+                }
+                TaskStore::Succeed { id } => {
+                    if let Some(task) = tasks.get_mut(&id) {
+                        task.status = TaskStatus::Succeeded;
+                        task.finished_at = Some(SystemTime::now());
+                        persist(&path, &tasks).await;
+                    }
+                }
+                TaskStore::Fail { id, reason } => {
+                    if let Some(task) = tasks.get_mut(&id) {
+                        task.status = TaskStatus::Failed { reason };
+                        task.finished_at = Some(SystemTime::now());
+                        persist(&path, &tasks).await;
+                    }
+                }
+                TaskStore::Get { id, tx } => {
+                    tx.send(tasks.get(&id).cloned()).unwrap_or_else(|_| {
+                        warn!("task_store::TaskStore::Get: unable to send response")
+                    });
+                }
+                TaskStore::List { filter, tx } => {
+                    tx.send(
+                        tasks
+                            .values()
+                            .filter(|task| filter.matches(task))
+                            .cloned()
+                            .collect(),
+                    )
+                    .unwrap_or_else(|_| {
+                        warn!("task_store::TaskStore::List: unable to send response")
+                    });
+                }
+                TaskStore::Stop => rx.close(),
+            }
+        }
+    });
+    Ok((tx, task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn encode_decode_round_trips_enqueued_task() {
+        let task = Task {
+            id: 7,
+            kind: TaskKind::AddIndex {
+                id: IndexId::from("my_index".to_string()),
+            },
+            status: TaskStatus::Enqueued,
+            created_at: at(1_700_000_000),
+            started_at: None,
+            finished_at: None,
+        };
+        let decoded = decode(&encode(&task)).expect("encoded task should decode");
+        assert_eq!(decoded, task);
+    }
+
+    #[test]
+    fn encode_decode_round_trips_failed_task_with_escaped_reason() {
+        let task = Task {
+            id: 9,
+            kind: TaskKind::DelIndex {
+                id: IndexId::from("other_index".to_string()),
+            },
+            status: TaskStatus::Failed {
+                reason: "boom\twith tab and\nnewline".to_string(),
+            },
+            created_at: at(1_700_000_000),
+            started_at: Some(at(1_700_000_005)),
+            finished_at: Some(at(1_700_000_010)),
+        };
+        let decoded = decode(&encode(&task)).expect("encoded task should decode");
+        // Tabs/newlines in the reason are replaced with spaces on encode, so the reason
+        // itself isn't expected to round-trip byte-for-byte -- only the rest of the task.
+        assert_eq!(decoded.id, task.id);
+        assert_eq!(decoded.kind, task.kind);
+        assert_eq!(decoded.created_at, task.created_at);
+        assert_eq!(decoded.started_at, task.started_at);
+        assert_eq!(decoded.finished_at, task.finished_at);
+        assert!(matches!(decoded.status, TaskStatus::Failed { reason } if !reason.contains('\t') && !reason.contains('\n')));
+    }
+
+    #[test]
+    fn decode_rejects_malformed_line() {
+        assert!(decode("not\tenough\tfields").is_none());
+    }
+}